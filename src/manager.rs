@@ -0,0 +1,129 @@
+//! Identity state: the fields a user has submitted for verification, and
+//! the per-field status/timestamp bookkeeping the verifier tracks.
+
+use crate::primitives::{unix_time, Account, ChallengeStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct FieldAddress(String);
+
+impl From<String> for FieldAddress {
+    fn from(value: String) -> Self {
+        FieldAddress(value)
+    }
+}
+
+impl FieldAddress {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum IdentityFieldType {
+    LegalName,
+    DisplayName,
+    Email,
+    Web,
+    Twitter,
+    Matrix,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum IdentityField {
+    LegalName(FieldAddress),
+    DisplayName(FieldAddress),
+    Email(FieldAddress),
+    Web(FieldAddress),
+    Twitter(FieldAddress),
+    Matrix(FieldAddress),
+}
+
+/// A single identity field together with its verification bookkeeping.
+/// `last_verified` and `next_check` are only ever updated together by
+/// `mark_verified`, so a freshly-accepted field is scheduled a full
+/// (jittered) interval out rather than reading as already overdue on the
+/// very next sweep.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Field {
+    field: IdentityField,
+    status: ChallengeStatus,
+    last_verified: u64,
+    next_check: u64,
+}
+
+impl Field {
+    pub fn new(field: IdentityField) -> Self {
+        Field {
+            field,
+            status: ChallengeStatus::Unconfirmed,
+            last_verified: 0,
+            next_check: 0,
+        }
+    }
+    pub fn field(&self) -> &IdentityField {
+        &self.field
+    }
+    pub fn mut_field(&mut self) -> &mut IdentityField {
+        &mut self.field
+    }
+    pub fn status(&self) -> &ChallengeStatus {
+        &self.status
+    }
+    pub fn last_verified(&self) -> u64 {
+        self.last_verified
+    }
+    /// When this field is next due for a re-verification sweep to even
+    /// look at it. Set explicitly (with jitter) by whoever last verified
+    /// it, rather than derived from `last_verified` + a flat interval, so
+    /// fields accepted in a burst don't all come due at once.
+    pub fn next_check(&self) -> u64 {
+        self.next_check
+    }
+    /// Records the outcome of a (re-)verification: the resulting status,
+    /// `last_verified` stamped to now, and `next_check` set to whatever the
+    /// caller has already computed (typically now + a jittered interval).
+    pub fn mark_verified(&mut self, status: ChallengeStatus, next_check: u64) {
+        self.status = status;
+        self.last_verified = unix_time();
+        self.next_check = next_check;
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct IdentityState {
+    pub net_account: Account,
+    pub fields: HashMap<IdentityFieldType, Field>,
+}
+
+impl IdentityState {
+    pub fn alice() -> Self {
+        let mut fields = HashMap::new();
+        fields.insert(
+            IdentityFieldType::Email,
+            Field::new(IdentityField::Email(FieldAddress::from(
+                "alice@email.com".to_string(),
+            ))),
+        );
+
+        IdentityState {
+            net_account: Account::from("alice"),
+            fields,
+        }
+    }
+    pub fn bob() -> Self {
+        let mut fields = HashMap::new();
+        fields.insert(
+            IdentityFieldType::Email,
+            Field::new(IdentityField::Email(FieldAddress::from(
+                "bob@email.com".to_string(),
+            ))),
+        );
+
+        IdentityState {
+            net_account: Account::from("bob"),
+            fields,
+        }
+    }
+}
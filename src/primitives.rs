@@ -1,10 +1,13 @@
 use base58::FromBase58;
+use blake2_rfc::blake2b::blake2b;
+use ed25519_dalek::{PublicKey as EdwardsPubKey, Signature as EdwardsSignature, Verifier};
 use failure::err_msg;
 
 use rand::{thread_rng, Rng};
 use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
 use schnorrkel::keys::PublicKey as SchnorrkelPubKey;
 use schnorrkel::sign::Signature as SchnorrkelSignature;
+use secp256k1::{ecdsa::Signature as EcdsaSignature, Message, PublicKey as EcdsaPubKey, Secp256k1};
 use serde::de::Error as SerdeError;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::convert::TryFrom;
@@ -23,18 +26,53 @@ pub fn unix_time() -> u64 {
         .as_secs()
 }
 
+// Holds the raw 32-byte key material regardless of the scheme it was
+// generated under. The concrete key type is only reconstructed on demand
+// (see `as_schnorr`/`as_edwards`/`as_ecdsa_candidates`), since which scheme applies is
+// a property of the `NetworkAddress` the key was parsed from, not of the
+// key bytes themselves.
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PubKey(SchnorrkelPubKey);
+pub struct PubKey([u8; 32]);
 
 impl PubKey {
     pub fn to_bytes(&self) -> [u8; 32] {
-        self.0.to_bytes()
+        self.0
+    }
+    /// Already a raw 32-byte array, so this is a plain round-trip rather
+    /// than a bincode encoding — there's nothing left to compact.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        PubKey::try_from(bytes.to_vec())
+    }
+    fn as_schnorr(&self) -> Result<SchnorrkelPubKey> {
+        SchnorrkelPubKey::from_bytes(&self.0).map_err(|_| err_msg("invalid schnorr public key"))
+    }
+    fn as_edwards(&self) -> Result<EdwardsPubKey> {
+        EdwardsPubKey::from_bytes(&self.0).map_err(|_| err_msg("invalid edwards public key"))
+    }
+    // The address format only reserves 32 bytes for the key, so the leading
+    // parity byte of the compressed secp256k1 encoding isn't available.
+    // Rather than guessing one parity and silently rejecting every key that
+    // happens to have the other, reconstruct both candidates and let the
+    // caller accept whichever one actually verifies.
+    fn as_ecdsa_candidates(&self) -> Result<[EcdsaPubKey; 2]> {
+        let mut even = [0u8; 33];
+        even[0] = 0x02;
+        even[1..].copy_from_slice(&self.0);
+
+        let mut odd = [0u8; 33];
+        odd[0] = 0x03;
+        odd[1..].copy_from_slice(&self.0);
+
+        Ok([
+            EcdsaPubKey::from_slice(&even).map_err(|_| err_msg("invalid ecdsa public key"))?,
+            EcdsaPubKey::from_slice(&odd).map_err(|_| err_msg("invalid ecdsa public key"))?,
+        ])
     }
 }
 
 impl From<SchnorrkelPubKey> for PubKey {
     fn from(value: SchnorrkelPubKey) -> Self {
-        PubKey(value)
+        PubKey(value.to_bytes())
     }
 }
 
@@ -42,9 +80,14 @@ impl TryFrom<Vec<u8>> for PubKey {
     type Error = failure::Error;
 
     fn try_from(value: Vec<u8>) -> Result<Self> {
-        Ok(PubKey(
-            SchnorrkelPubKey::from_bytes(&value).map_err(|_| err_msg("invalid public key"))?,
-        ))
+        if value.len() != 32 {
+            return Err(err_msg("invalid public key"));
+        }
+
+        let mut bytes = [0; 32];
+        bytes.copy_from_slice(&value);
+
+        Ok(PubKey(bytes))
     }
 }
 
@@ -63,22 +106,105 @@ impl<'de> Deserialize<'de> for PubKey {
         D: Deserializer<'de>,
     {
         let hex_str = <String as Deserialize>::deserialize(deserializer)?;
-        Ok(PubKey(
-            SchnorrkelPubKey::from_bytes(
-                &hex::decode(hex_str)
-                    .map_err(|_| SerdeError::custom("failed to decode public key from hex"))?,
-            )
-            .map_err(|_| SerdeError::custom("failed creating public key from bytes"))?,
-        ))
+        PubKey::try_from(
+            hex::decode(hex_str)
+                .map_err(|_| SerdeError::custom("failed to decode public key from hex"))?,
+        )
+        .map_err(|_| SerdeError::custom("failed creating public key from bytes"))
     }
 }
 
+/// A signature tied to one of the supported key schemes. Which variant a
+/// serialized signature should be parsed into depends on the `Algorithm`
+/// detected for the associated `NetworkAddress` (see `Signature::from_bytes`),
+/// since the raw bytes alone don't self-describe their scheme.
 #[derive(Eq, PartialEq, Clone, Debug)]
-pub struct Signature(SchnorrkelSignature);
+pub enum Signature {
+    Schnorr(SchnorrkelSignature),
+    Edwards(EdwardsSignature),
+    Ecdsa(EcdsaSignature),
+}
+
+impl Signature {
+    pub fn from_bytes(algo: &Algorithm, bytes: &[u8]) -> Result<Self> {
+        use Algorithm::*;
+
+        match algo {
+            Schnorr => Ok(Signature::Schnorr(
+                SchnorrkelSignature::from_bytes(bytes)
+                    .map_err(|_| err_msg("invalid schnorr signature"))?,
+            )),
+            Edwards => Ok(Signature::Edwards(
+                EdwardsSignature::from_bytes(bytes)
+                    .map_err(|_| err_msg("invalid edwards signature"))?,
+            )),
+            ECDSA => Ok(Signature::Ecdsa(
+                EcdsaSignature::from_compact(bytes)
+                    .map_err(|_| err_msg("invalid ecdsa signature"))?,
+            )),
+            Unknown(_) => Err(err_msg(
+                "unsupported algorithm, cannot construct a signature for it",
+            )),
+        }
+    }
+
+    /// Encodes the signature as a compact, self-describing byte string (the
+    /// bincode encoding of an algorithm tag followed by the length-prefixed
+    /// raw signature bytes) for binary
+    /// storage, as opposed to the hex/JSON encodings used by human-facing
+    /// APIs elsewhere in this module.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let (tag, raw): (u8, Vec<u8>) = match self {
+            Signature::Schnorr(sig) => (0, sig.to_bytes().to_vec()),
+            Signature::Edwards(sig) => (1, sig.to_bytes().to_vec()),
+            Signature::Ecdsa(sig) => (2, sig.serialize_compact().to_vec()),
+        };
+
+        bincode::serialize(&(tag, raw)).expect("bincode serialization of Signature is infallible")
+    }
+
+    /// Inverse of `to_bytes`: recovers the algorithm tag and raw signature
+    /// bytes it was encoded with, then parses the matching variant.
+    pub fn from_tagged_bytes(bytes: &[u8]) -> Result<Self> {
+        let (tag, raw): (u8, Vec<u8>) = bincode::deserialize(bytes)
+            .map_err(|_| err_msg("failed to decode signature from bytes"))?;
+
+        let algo = match tag {
+            0 => Algorithm::Schnorr,
+            1 => Algorithm::Edwards,
+            2 => Algorithm::ECDSA,
+            other => Algorithm::Unknown(other),
+        };
+
+        Signature::from_bytes(&algo, &raw)
+    }
+}
 
 impl From<SchnorrkelSignature> for Signature {
     fn from(value: SchnorrkelSignature) -> Self {
-        Signature(value)
+        Signature::Schnorr(value)
+    }
+}
+
+// `Signature` has no human-facing hex/JSON representation to preserve, so
+// its only codec is this compact binary one, used to persist signatures in
+// the event log instead of omitting them.
+impl Serialize for Signature {
+    fn serialize<S>(&self, serializer: S) -> StdResult<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&self.to_bytes())
+    }
+}
+
+impl<'de> Deserialize<'de> for Signature {
+    fn deserialize<D>(deserializer: D) -> StdResult<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes = <Vec<u8> as Deserialize>::deserialize(deserializer)?;
+        Signature::from_tagged_bytes(&bytes).map_err(SerdeError::custom)
     }
 }
 
@@ -177,14 +303,57 @@ pub struct NetworkAddress {
     pub_key: PubKey,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Algorithm {
-    #[serde(rename = "schnorr")]
     Schnorr,
-    #[serde(rename = "edwards")]
     Edwards,
-    #[serde(rename = "ecdsa")]
     ECDSA,
+    // Preserves the raw key-type discriminant so identities using a scheme
+    // this crate doesn't (yet) implement can still be loaded, stored and
+    // displayed, rather than being rejected outright at parse time.
+    Unknown(u8),
+}
+
+// Derived `Serialize`/`Deserialize` would give the unit variants a plain
+// string shape (`"schnorr"`) but `Unknown(u8)` an object shape
+// (`{"unknown":52}`) under serde's default externally-tagged
+// representation, breaking any consumer (config, HTTP API, DB column)
+// expecting `Algorithm` to always be a bare string. Encode every variant as
+// a string, with `Unknown` spelled `"unknown:<byte>"`.
+impl Serialize for Algorithm {
+    fn serialize<S>(&self, serializer: S) -> StdResult<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Algorithm::Schnorr => serializer.serialize_str("schnorr"),
+            Algorithm::Edwards => serializer.serialize_str("edwards"),
+            Algorithm::ECDSA => serializer.serialize_str("ecdsa"),
+            Algorithm::Unknown(byte) => serializer.serialize_str(&format!("unknown:{}", byte)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Algorithm {
+    fn deserialize<D>(deserializer: D) -> StdResult<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = <String as Deserialize>::deserialize(deserializer)?;
+
+        Ok(match value.as_str() {
+            "schnorr" => Algorithm::Schnorr,
+            "edwards" => Algorithm::Edwards,
+            "ecdsa" => Algorithm::ECDSA,
+            other => {
+                let byte = other
+                    .strip_prefix("unknown:")
+                    .and_then(|byte| byte.parse::<u8>().ok())
+                    .ok_or_else(|| SerdeError::custom("unrecognized algorithm"))?;
+                Algorithm::Unknown(byte)
+            }
+        })
+    }
 }
 
 impl NetworkAddress {
@@ -197,6 +366,40 @@ impl NetworkAddress {
     pub fn algo(&self) -> &Algorithm {
         &self.algo
     }
+    /// Compact binary encoding of the address, algorithm and public key,
+    /// for event-log storage. Bypasses `NetworkAddress`'s derived
+    /// `Serialize`, which goes through `PubKey`'s hex encoding and is meant
+    /// for human-facing APIs rather than compact storage.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let algo_byte = match &self.algo {
+            Algorithm::Schnorr => 48,
+            Algorithm::Edwards => 49,
+            Algorithm::ECDSA => 50,
+            Algorithm::Unknown(byte) => *byte,
+        };
+
+        bincode::serialize(&(self.address.as_str(), algo_byte, self.pub_key.to_bytes()))
+            .expect("bincode serialization of NetworkAddress is infallible")
+    }
+    /// Inverse of `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let (address, algo_byte, pub_key_bytes): (String, u8, [u8; 32]) =
+            bincode::deserialize(bytes)
+                .map_err(|_| err_msg("failed to decode NetworkAddress from bytes"))?;
+
+        let algo = match algo_byte {
+            48 => Algorithm::Schnorr,
+            49 => Algorithm::Edwards,
+            50 => Algorithm::ECDSA,
+            other => Algorithm::Unknown(other),
+        };
+
+        Ok(NetworkAddress {
+            address: NetAccount::from(address),
+            algo,
+            pub_key: PubKey::from_bytes(&pub_key_bytes)?,
+        })
+    }
 }
 
 impl TryFrom<NetAccount> for NetworkAddress {
@@ -212,11 +415,11 @@ impl TryFrom<NetAccount> for NetworkAddress {
             return Err(err_msg("invalid address"));
         }
 
-        let algo = match &bytes[1] {
+        let algo = match bytes[1] {
             48 => Algorithm::Schnorr,
             49 => Algorithm::Edwards,
             50 => Algorithm::ECDSA,
-            _ => return Err(err_msg("failed to detect address algorithm")),
+            other => Algorithm::Unknown(other),
         };
 
         Ok(NetworkAddress {
@@ -323,27 +526,75 @@ impl FromSql for ChallengeStatus {
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Challenge(pub String);
 
+/// Outcome of checking a `Signature` against a `Challenge`, distinguishing a
+/// genuinely bad signature from an `Algorithm` this crate doesn't know how to
+/// verify, so operators aren't told "invalid" for a scheme we simply never
+/// implemented.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ChallengeVerification {
+    #[serde(rename = "valid")]
+    Valid,
+    #[serde(rename = "invalid")]
+    Invalid,
+    #[serde(rename = "unsupported")]
+    Unsupported,
+}
+
+impl ChallengeVerification {
+    pub fn is_valid(&self) -> bool {
+        *self == ChallengeVerification::Valid
+    }
+}
+
 impl Challenge {
     pub fn gen_random() -> Challenge {
         let random: [u8; 16] = thread_rng().gen();
         Challenge(hex::encode(random))
     }
-    pub fn verify_challenge(&self, network_address: &NetworkAddress, sig: &Signature) -> bool {
-        use Algorithm::*;
-
-        let pub_key = network_address.pub_key().0;
-        match network_address.algo() {
-            Schnorr => {
-                pub_key
-                    .verify_simple(b"substrate", self.0.as_bytes(), &sig.0)
-                    .is_ok()
-            }
-            Edwards => {
-                false
-            }
-            ECDSA => {
-                false
-            }
+    pub fn verify_challenge(
+        &self,
+        network_address: &NetworkAddress,
+        sig: &Signature,
+    ) -> ChallengeVerification {
+        use ChallengeVerification::*;
+
+        match (network_address.algo(), sig) {
+            (Algorithm::Schnorr, Signature::Schnorr(sig)) => network_address
+                .pub_key()
+                .as_schnorr()
+                .map(|pub_key| {
+                    pub_key
+                        .verify_simple(b"substrate", self.0.as_bytes(), sig)
+                        .is_ok()
+                })
+                .map(|valid| if valid { Valid } else { Invalid })
+                .unwrap_or(Invalid),
+            (Algorithm::Edwards, Signature::Edwards(sig)) => network_address
+                .pub_key()
+                .as_edwards()
+                .map(|pub_key| pub_key.verify(self.0.as_bytes(), sig).is_ok())
+                .map(|valid| if valid { Valid } else { Invalid })
+                .unwrap_or(Invalid),
+            (Algorithm::ECDSA, Signature::Ecdsa(sig)) => network_address
+                .pub_key()
+                .as_ecdsa_candidates()
+                .map(|candidates| {
+                    // Substrate's `ecdsa::Pair` signs the blake2_256 digest
+                    // of the message, not the message itself.
+                    let digest = blake2b(32, &[], self.0.as_bytes());
+                    let message = Message::from_slice(digest.as_bytes())
+                        .expect("blake2b(32, ..) always yields a 32-byte message");
+                    let engine = Secp256k1::verification_only();
+
+                    candidates
+                        .iter()
+                        .any(|pub_key| engine.verify_ecdsa(&message, sig, pub_key).is_ok())
+                })
+                .map(|valid| if valid { Valid } else { Invalid })
+                .unwrap_or(Invalid),
+            (Algorithm::Unknown(_), _) => Unsupported,
+            // The signature scheme doesn't match the address' detected algorithm.
+            _ => Invalid,
         }
     }
     pub fn as_str(&self) -> &str {
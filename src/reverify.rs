@@ -0,0 +1,164 @@
+//! Periodic re-verification of `Accepted` identity fields.
+//!
+//! Once a field is trusted we still need to notice when the underlying
+//! email/Twitter/Matrix account has since been deleted or changed hands.
+//! This module owns only the scheduling: when a field is due for another
+//! look, and how to spread those checks out over time so a burst of
+//! identities verified together doesn't all re-check at once. The actual
+//! account probe is supplied by a [`Reverifier`] implementation per
+//! `AccountType`, and every resulting state change is fed back through the
+//! existing `VerifierCommand::InsertIdentity` flow so it lands in the
+//! normal event log rather than mutating state out of band.
+
+use crate::aggregate::verifier::{VerifierAggregate, VerifierAggregateId, VerifierCommand};
+use crate::manager::{Field, IdentityFieldType, IdentityState};
+use crate::primitives::{unix_time, ChallengeStatus, Result};
+use eventually::Repository;
+use failure::err_msg;
+use rand::{thread_rng, Rng};
+use std::future::Future;
+use std::time::Duration;
+
+/// Base interval between re-verifications of an `Accepted` field, before
+/// jitter is applied. On the order of a week, since these accounts are
+/// expected to drift away from their owner slowly rather than needing
+/// near-real-time revocation.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Floor on the jittered delay, so an unlucky draw can't schedule a field's
+/// next check moments after its last one.
+pub(crate) const MIN_DELAY: Duration = Duration::from_secs(5 * 60);
+
+/// Picks a field's next re-verification delay uniformly over
+/// `[0, 2 * interval)`, with a floor of a few minutes, so identities
+/// verified in a burst don't all re-check at the same moment. Called once
+/// per field, at the moment it's (re-)verified, and the result persisted
+/// as that field's `next_check` — not recomputed on every sweep, which
+/// would let the window keep sliding instead of converging on a due time.
+pub(crate) fn jittered_delay(interval: Duration) -> Duration {
+    let upper_secs = interval.as_secs().saturating_mul(2).max(1);
+    let delay_secs = thread_rng().gen_range(0..upper_secs);
+    Duration::from_secs(delay_secs).max(MIN_DELAY)
+}
+
+/// Re-runs the external account check (email ping, Twitter mention lookup,
+/// Matrix room membership, ...) for a single field. Implemented per
+/// `AccountType` by the adapters that already know how to talk to each
+/// service; this module only owns the scheduling around it.
+pub trait Reverifier {
+    fn reverify(&self, field: &Field) -> Result<bool>;
+}
+
+/// Schedules and drives periodic re-verification of `Accepted` fields.
+/// Each field's `last_verified`/`next_check` (persisted on the field
+/// itself, via `Field::mark_verified`) is the scheduler's only state, so
+/// due items can always be recomputed fresh on startup instead of
+/// requiring separate scheduler bookkeeping.
+pub struct ReverificationScheduler<R: Reverifier> {
+    interval: Duration,
+    reverifier: R,
+}
+
+impl<R: Reverifier> ReverificationScheduler<R> {
+    pub fn new(interval: Duration, reverifier: R) -> Self {
+        ReverificationScheduler {
+            interval,
+            reverifier,
+        }
+    }
+
+    /// Returns every `Accepted` field across `identities` whose persisted
+    /// `next_check` has passed, due for an immediate check. Using each
+    /// field's own jittered `next_check` (rather than a flat
+    /// `last_verified + interval` cutoff) is what actually spreads a batch
+    /// of identities accepted together across time, instead of all of them
+    /// landing in the same sweep.
+    pub fn due<'a>(
+        &self,
+        identities: &'a [IdentityState],
+    ) -> Vec<(&'a IdentityState, &'a IdentityFieldType)> {
+        let now = unix_time();
+
+        identities
+            .iter()
+            .flat_map(|identity| {
+                identity
+                    .fields
+                    .iter()
+                    .filter(move |(_, field)| {
+                        *field.status() == ChallengeStatus::Accepted && now >= field.next_check()
+                    })
+                    .map(move |(field_type, _)| (identity, field_type))
+            })
+            .collect()
+    }
+
+    /// Re-challenges a single field and feeds the resulting state change
+    /// back through the aggregate: `Accepted` (with a freshly jittered
+    /// `next_check`) if the account still responds, downgraded to
+    /// `Unconfirmed` if it no longer does.
+    async fn recheck(
+        &self,
+        repo: &mut Repository<VerifierAggregateId, VerifierAggregate>,
+        mut identity: IdentityState,
+        field_type: &IdentityFieldType,
+    ) -> Result<()> {
+        let still_valid = match identity.fields.get(field_type) {
+            Some(field) => self.reverifier.reverify(field)?,
+            None => return Ok(()),
+        };
+
+        if let Some(field) = identity.fields.get_mut(field_type) {
+            let status = if still_valid {
+                ChallengeStatus::Accepted
+            } else {
+                ChallengeStatus::Unconfirmed
+            };
+            let next_check = unix_time() + jittered_delay(self.interval).as_secs();
+            field.mark_verified(status, next_check);
+        }
+
+        let mut root = repo
+            .get(VerifierAggregateId)
+            .await
+            .map_err(|err| err_msg(format!("failed to load verifier aggregate: {:?}", err)))?;
+
+        root.handle(VerifierCommand::InsertIdentity(identity))
+            .await
+            .map_err(|err| err_msg(format!("failed to re-verify identity field: {:?}", err)))?;
+
+        repo.add(root)
+            .await
+            .map_err(|err| err_msg(format!("failed to commit re-verification: {:?}", err)))
+    }
+
+    /// Runs the scheduler forever: every sweep rechecks all due fields,
+    /// then sleeps until the next sweep. `fetch_identities` is left to the
+    /// caller, since it owns the current source of truth for identity
+    /// state.
+    pub async fn run<F, Fut>(
+        &self,
+        repo: &mut Repository<VerifierAggregateId, VerifierAggregate>,
+        fetch_identities: F,
+    ) -> Result<()>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<Vec<IdentityState>>>,
+    {
+        // A short, fixed poll interval for noticing newly-due fields — the
+        // actual spacing between any two re-checks of the *same* field
+        // comes entirely from its persisted, jittered `next_check`, not
+        // from this loop's cadence.
+        const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+        loop {
+            let identities = fetch_identities().await?;
+
+            for (identity, field_type) in self.due(&identities) {
+                self.recheck(repo, identity.clone(), field_type).await?;
+            }
+
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
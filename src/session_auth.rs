@@ -0,0 +1,119 @@
+//! Challenge-response session authentication for event subscribers.
+//!
+//! `AccountType::ReservedConnector`, `ReservedEmitter` and `ReservedFeeder`
+//! are reserved for internal communication, but nothing previously proved a
+//! connecting client actually controlled one of those keys before letting it
+//! subscribe to (or emit into) the verifier's event stream. This module adds
+//! that handshake: the server issues a fresh `Challenge::gen_random()`
+//! nonce, the client signs it, and `Challenge::verify_challenge` gates
+//! whether the connection is upgraded — scoped per role so an emitter key
+//! can't masquerade as a connector.
+
+use crate::primitives::{AccountType, Challenge, NetworkAddress, Result, Signature};
+use failure::err_msg;
+use std::collections::HashMap;
+
+/// Which internal role a connecting subscriber is authenticating as. Mirrors
+/// the crate's reserved `AccountType`s for internal communication.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ReservedRole {
+    Connector,
+    Emitter,
+    Feeder,
+}
+
+impl ReservedRole {
+    fn account_type(&self) -> AccountType {
+        match self {
+            ReservedRole::Connector => AccountType::ReservedConnector,
+            ReservedRole::Emitter => AccountType::ReservedEmitter,
+            ReservedRole::Feeder => AccountType::ReservedFeeder,
+        }
+    }
+}
+
+/// Authentication settings for the verifier's event subscription endpoint.
+/// Disabled by default, like the other relays gate optional behavior behind
+/// an explicit `enable_auth` flag.
+#[derive(Clone, Debug)]
+pub struct AuthConfig {
+    pub enable_auth: bool,
+    permitted: HashMap<ReservedRole, Vec<NetworkAddress>>,
+}
+
+impl AuthConfig {
+    pub fn disabled() -> Self {
+        AuthConfig {
+            enable_auth: false,
+            permitted: HashMap::new(),
+        }
+    }
+
+    pub fn new(permitted: HashMap<ReservedRole, Vec<NetworkAddress>>) -> Self {
+        AuthConfig {
+            enable_auth: true,
+            permitted,
+        }
+    }
+
+    fn is_permitted(&self, role: &ReservedRole, address: &NetworkAddress) -> bool {
+        self.permitted
+            .get(role)
+            .map(|addresses| addresses.contains(address))
+            .unwrap_or(false)
+    }
+}
+
+/// Minimal duplex the handshake needs from a connecting client: send the
+/// challenge nonce, then receive back the address/signature pair it
+/// produced. Kept separate from any concrete transport (WebSocket, TCP, ...)
+/// so the handshake logic doesn't depend on one.
+#[async_trait::async_trait]
+pub trait AuthTransport {
+    async fn send_challenge(&mut self, challenge: &Challenge) -> Result<()>;
+    async fn recv_response(&mut self) -> Result<(NetworkAddress, Signature)>;
+}
+
+/// Authenticates a connecting subscriber for one of the reserved internal
+/// roles before it's allowed to subscribe to or emit events.
+pub struct SessionAuthenticator {
+    config: AuthConfig,
+}
+
+impl SessionAuthenticator {
+    pub fn new(config: AuthConfig) -> Self {
+        SessionAuthenticator { config }
+    }
+
+    /// Runs the handshake: issue a fresh nonce, verify the signed response
+    /// against it, and check the signing key is permitted for `role`. When
+    /// auth is disabled (`enable_auth` is false), every connection is let
+    /// through unscoped.
+    pub async fn authenticate<T: AuthTransport + Send>(
+        &self,
+        transport: &mut T,
+        role: ReservedRole,
+    ) -> Result<()> {
+        if !self.config.enable_auth {
+            return Ok(());
+        }
+
+        let challenge = Challenge::gen_random();
+        transport.send_challenge(&challenge).await?;
+
+        let (address, sig) = transport.recv_response().await?;
+
+        if !challenge.verify_challenge(&address, &sig).is_valid() {
+            return Err(err_msg("challenge signature did not verify"));
+        }
+
+        if !self.config.is_permitted(&role, &address) {
+            return Err(err_msg(format!(
+                "key is not permitted for the {:?} role",
+                role.account_type()
+            )));
+        }
+
+        Ok(())
+    }
+}
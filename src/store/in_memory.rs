@@ -0,0 +1,78 @@
+//! In-memory [`EventStore`], used by tests.
+//!
+//! Implementing the same trait `PostgresBackend` does — rather than just
+//! happening to expose similarly-named methods — is what actually makes
+//! the two backends interchangeable: `src/tests/store_interop.rs` runs one
+//! shared test body against both.
+
+use super::{ConcurrencyConflict, EventStore, EventSubscription, Sequence};
+use crate::primitives::Result;
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::sync::broadcast;
+
+pub struct InMemoryBackend {
+    streams: Mutex<HashMap<String, Vec<JsonValue>>>,
+    notifications: broadcast::Sender<String>,
+}
+
+impl InMemoryBackend {
+    pub fn new() -> Self {
+        let (notifications, _) = broadcast::channel(1024);
+        InMemoryBackend {
+            streams: Mutex::new(HashMap::new()),
+            notifications,
+        }
+    }
+}
+
+impl Default for InMemoryBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl EventStore for InMemoryBackend {
+    async fn append(
+        &self,
+        aggregate_id: &str,
+        expected_sequence: Option<Sequence>,
+        bodies: Vec<JsonValue>,
+    ) -> Result<std::result::Result<(), ConcurrencyConflict>> {
+        let mut streams = self.streams.lock().unwrap();
+        let stream = streams.entry(aggregate_id.to_owned()).or_default();
+
+        if stream.len() as Sequence != expected_sequence.unwrap_or(0) {
+            return Ok(Err(ConcurrencyConflict));
+        }
+
+        stream.extend(bodies);
+        drop(streams);
+
+        let _ = self.notifications.send(aggregate_id.to_owned());
+
+        Ok(Ok(()))
+    }
+
+    async fn load(&self, aggregate_id: &str) -> Result<Vec<(Sequence, JsonValue)>> {
+        let streams = self.streams.lock().unwrap();
+        Ok(streams
+            .get(aggregate_id)
+            .into_iter()
+            .flatten()
+            .cloned()
+            .enumerate()
+            .map(|(index, body)| (index as Sequence + 1, body))
+            .collect())
+    }
+
+    async fn subscribe(&self, aggregate_id: &str) -> Result<EventSubscription> {
+        Ok(EventSubscription {
+            aggregate_id: aggregate_id.to_owned(),
+            receiver: self.notifications.subscribe(),
+        })
+    }
+}
@@ -0,0 +1,76 @@
+//! Pluggable durable storage for the verifier's event log.
+//!
+//! Both backends key events the same way (aggregate id, per-aggregate
+//! sequence number, JSON body) and implement the same [`EventStore`] trait,
+//! so a `VerifierAggregate` can be pointed at either one without changing
+//! how the aggregate itself works: [`in_memory::InMemoryBackend`] backs the
+//! test suite, [`postgres::PostgresBackend`] survives a restart.
+
+pub mod in_memory;
+pub mod postgres;
+
+pub use self::in_memory::InMemoryBackend;
+pub use self::postgres::{PostgresBackend, PostgresConfig};
+
+use crate::primitives::Result;
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use tokio::sync::broadcast;
+
+pub type Sequence = i64;
+
+/// Returned by [`EventStore::append`] when another writer has already
+/// appended to this aggregate's stream since the caller last read it,
+/// rather than the caller's events silently clobbering what's already
+/// there.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConcurrencyConflict;
+
+/// A durable event log, abstracted over the backend actually holding it.
+/// Every method operates on an aggregate's raw JSON event bodies, so
+/// callers that want typed events (see `PostgresBackend::append`/`load`)
+/// layer `serde_json::to_value`/`from_value` on top rather than this trait
+/// having to be generic over the event type itself.
+#[async_trait]
+pub trait EventStore: Send + Sync {
+    /// Appends `bodies` right after `expected_sequence` (`None` for a
+    /// brand-new stream) to `aggregate_id`'s stream, as a single atomic
+    /// unit: if any event in the batch can't be appended because another
+    /// writer already claimed its sequence number, none of them are, so
+    /// callers never have to reason about a half-written batch.
+    async fn append(
+        &self,
+        aggregate_id: &str,
+        expected_sequence: Option<Sequence>,
+        bodies: Vec<JsonValue>,
+    ) -> Result<std::result::Result<(), ConcurrencyConflict>>;
+
+    /// Loads every event persisted for `aggregate_id`, ordered by sequence.
+    async fn load(&self, aggregate_id: &str) -> Result<Vec<(Sequence, JsonValue)>>;
+
+    /// Subscribes to newly-appended events for `aggregate_id`.
+    async fn subscribe(&self, aggregate_id: &str) -> Result<EventSubscription>;
+}
+
+/// A live subscription to one aggregate's event log, fed by whichever
+/// backend created it.
+pub struct EventSubscription {
+    pub(crate) aggregate_id: String,
+    pub(crate) receiver: broadcast::Receiver<String>,
+}
+
+impl EventSubscription {
+    /// Waits until `aggregate_id` has a newly-appended event, skipping
+    /// notifications for other aggregates sharing the same channel.
+    pub async fn recv(&mut self) -> Result<()> {
+        loop {
+            let notified_id = self.receiver.recv().await.map_err(|err| {
+                failure::err_msg(format!("subscription lagged or closed: {}", err))
+            })?;
+
+            if notified_id == self.aggregate_id {
+                return Ok(());
+            }
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use crate::primitives::Result;
+use crate::store::{ConcurrencyConflict, EventStore, EventSubscription, Sequence};
+use async_trait::async_trait;
+use failure::err_msg;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value as JsonValue;
+use tokio::sync::{broadcast, Mutex};
+use tokio_postgres::{Client, NoTls};
+
+/// Connection details for the durable event store. Kept separate from
+/// `tokio_postgres::Config` so it can be parsed straight out of the
+/// application config file.
+#[derive(Clone, Debug)]
+pub struct PostgresConfig {
+    pub host: String,
+    pub port: u16,
+    pub user: String,
+    pub password: String,
+    pub dbname: String,
+}
+
+impl PostgresConfig {
+    fn to_tokio_postgres_config(&self) -> tokio_postgres::Config {
+        let mut config = tokio_postgres::Config::new();
+        config
+            .host(&self.host)
+            .port(self.port)
+            .user(&self.user)
+            .password(&self.password)
+            .dbname(&self.dbname);
+
+        config
+    }
+
+    /// Reads connection details from the standard `PG*` environment
+    /// variables, returning `None` if `PGDATABASE` isn't set. Used by the
+    /// integration tests in `src/tests/store_postgres.rs` and
+    /// `src/tests/store_interop.rs`, which are skipped unless a real
+    /// database is configured.
+    pub fn from_env() -> Option<Self> {
+        Some(PostgresConfig {
+            host: std::env::var("PGHOST").unwrap_or_else(|_| "localhost".to_owned()),
+            port: std::env::var("PGPORT")
+                .ok()
+                .and_then(|port| port.parse().ok())
+                .unwrap_or(5432),
+            user: std::env::var("PGUSER").unwrap_or_else(|_| "postgres".to_owned()),
+            password: std::env::var("PGPASSWORD").unwrap_or_default(),
+            dbname: std::env::var("PGDATABASE").ok()?,
+        })
+    }
+}
+
+const CREATE_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS event_log (
+        aggregate_id TEXT NOT NULL,
+        sequence BIGINT NOT NULL,
+        body JSONB NOT NULL,
+        PRIMARY KEY (aggregate_id, sequence)
+    )
+";
+
+/// A Postgres-backed event store. Every event is persisted with its
+/// aggregate id, a per-aggregate sequence number and a JSON body; `append`
+/// enforces optimistic concurrency via the `(aggregate_id, sequence)`
+/// primary key, so a writer racing another append for the same sequence
+/// number simply fails to insert instead of overwriting it. New events are
+/// announced to subscribers with `NOTIFY`, mirroring the live updates the
+/// in-memory backend already provides so the two stores stay
+/// interchangeable from the aggregate's point of view.
+///
+/// `client` is behind a `Mutex` (rather than `tokio_postgres::Client`'s own
+/// internal sharing) because `append` needs a `&mut Client` to open a
+/// transaction, and a single connection can only have one transaction open
+/// at a time regardless.
+pub struct PostgresBackend {
+    client: Mutex<Client>,
+    notifications: broadcast::Sender<String>,
+}
+
+impl PostgresBackend {
+    pub async fn connect(config: &PostgresConfig) -> Result<Self> {
+        let (client, mut connection) = config
+            .to_tokio_postgres_config()
+            .connect(NoTls)
+            .await
+            .map_err(|err| err_msg(format!("failed to connect to Postgres: {}", err)))?;
+
+        let (notifications, _) = broadcast::channel(1024);
+        let notifications_tx = notifications.clone();
+
+        tokio::spawn(async move {
+            use futures_util::future::poll_fn;
+
+            loop {
+                match poll_fn(|cx| connection.poll_message(cx)).await {
+                    Some(Ok(tokio_postgres::AsyncMessage::Notification(note))) => {
+                        let _ = notifications_tx.send(note.payload().to_owned());
+                    }
+                    Some(Ok(_)) => continue,
+                    Some(Err(err)) => {
+                        eprintln!("Postgres connection error: {}", err);
+                        break;
+                    }
+                    None => break,
+                }
+            }
+        });
+
+        client
+            .batch_execute(CREATE_SCHEMA)
+            .await
+            .map_err(|err| err_msg(format!("failed to create event_log schema: {}", err)))?;
+
+        Ok(PostgresBackend {
+            client: Mutex::new(client),
+            notifications,
+        })
+    }
+
+    /// Typed convenience wrapper around [`EventStore::append`], for callers
+    /// that already have a concrete event type to persist rather than a raw
+    /// JSON body.
+    pub async fn append<Event>(
+        &self,
+        aggregate_id: &str,
+        expected_sequence: Option<Sequence>,
+        events: &[Event],
+    ) -> Result<std::result::Result<(), ConcurrencyConflict>>
+    where
+        Event: Serialize,
+    {
+        let bodies = events
+            .iter()
+            .map(|event| {
+                serde_json::to_value(event)
+                    .map_err(|err| err_msg(format!("failed to encode event body: {}", err)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        EventStore::append(self, aggregate_id, expected_sequence, bodies).await
+    }
+
+    /// Typed convenience wrapper around [`EventStore::load`].
+    pub async fn load<Event>(&self, aggregate_id: &str) -> Result<Vec<(Sequence, Event)>>
+    where
+        Event: DeserializeOwned,
+    {
+        EventStore::load(self, aggregate_id)
+            .await?
+            .into_iter()
+            .map(|(sequence, body)| {
+                let event = serde_json::from_value(body)
+                    .map_err(|err| err_msg(format!("failed to decode event body: {}", err)))?;
+
+                Ok((sequence, event))
+            })
+            .collect()
+    }
+
+    /// Subscribes to newly-appended events for `aggregate_id` via
+    /// Postgres' `LISTEN`/`NOTIFY`.
+    pub async fn subscribe(&self, aggregate_id: &str) -> Result<EventSubscription> {
+        EventStore::subscribe(self, aggregate_id).await
+    }
+}
+
+#[async_trait]
+impl EventStore for PostgresBackend {
+    /// Appends `bodies` right after `expected_sequence` (`None` for a
+    /// brand-new stream) inside a single Postgres transaction, so a
+    /// conflict partway through the batch rolls back every event already
+    /// inserted earlier in the same call instead of leaving a truncated
+    /// stream behind. Subscribers are only `NOTIFY`'d once the whole batch
+    /// has actually committed.
+    async fn append(
+        &self,
+        aggregate_id: &str,
+        expected_sequence: Option<Sequence>,
+        bodies: Vec<JsonValue>,
+    ) -> Result<std::result::Result<(), ConcurrencyConflict>> {
+        let mut client = self.client.lock().await;
+        let txn = client
+            .transaction()
+            .await
+            .map_err(|err| err_msg(format!("failed to start transaction: {}", err)))?;
+
+        let mut next_sequence = expected_sequence.unwrap_or(0) + 1;
+
+        for body in &bodies {
+            let inserted = txn
+                .execute(
+                    "INSERT INTO event_log (aggregate_id, sequence, body)
+                     VALUES ($1, $2, $3)
+                     ON CONFLICT (aggregate_id, sequence) DO NOTHING",
+                    &[&aggregate_id, &next_sequence, body],
+                )
+                .await
+                .map_err(|err| err_msg(format!("failed to append event: {}", err)))?;
+
+            if inserted == 0 {
+                // Dropping `txn` here rolls it back, undoing every insert
+                // already made earlier in this batch.
+                return Ok(Err(ConcurrencyConflict));
+            }
+
+            next_sequence += 1;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|err| err_msg(format!("failed to commit transaction: {}", err)))?;
+
+        client
+            .execute("SELECT pg_notify('event_log', $1)", &[&aggregate_id])
+            .await
+            .map_err(|err| err_msg(format!("failed to notify subscribers: {}", err)))?;
+
+        Ok(Ok(()))
+    }
+
+    async fn load(&self, aggregate_id: &str) -> Result<Vec<(Sequence, JsonValue)>> {
+        let client = self.client.lock().await;
+        let rows = client
+            .query(
+                "SELECT sequence, body FROM event_log
+                 WHERE aggregate_id = $1
+                 ORDER BY sequence ASC",
+                &[&aggregate_id],
+            )
+            .await
+            .map_err(|err| err_msg(format!("failed to load event stream: {}", err)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let sequence: Sequence = row.get(0);
+                let body: JsonValue = row.get(1);
+                (sequence, body)
+            })
+            .collect())
+    }
+
+    async fn subscribe(&self, aggregate_id: &str) -> Result<EventSubscription> {
+        let client = self.client.lock().await;
+        client
+            .batch_execute("LISTEN event_log")
+            .await
+            .map_err(|err| err_msg(format!("failed to subscribe to event_log: {}", err)))?;
+
+        Ok(EventSubscription {
+            aggregate_id: aggregate_id.to_owned(),
+            receiver: self.notifications.subscribe(),
+        })
+    }
+}
@@ -0,0 +1,9 @@
+mod codec;
+mod primitives;
+mod session_auth;
+mod store_interop;
+mod store_postgres;
+
+// `reverify.rs` isn't part of the module tree yet (see the comment in
+// `lib.rs`), so `tests/reverify.rs` — which reaches it via `crate::reverify`
+// — stays unregistered alongside it, same as `verifier_aggregate.rs`.
@@ -0,0 +1,143 @@
+use crate::primitives::{Algorithm, Challenge, ChallengeVerification, NetworkAddress, Signature};
+use ed25519_dalek::{Keypair as EdwardsKeypair, Signer};
+use secp256k1::Secp256k1;
+
+fn network_address(algo_byte: u8, pub_key: [u8; 32]) -> NetworkAddress {
+    let encoded = bincode::serialize(&("test-address", algo_byte, pub_key)).unwrap();
+    NetworkAddress::from_bytes(&encoded).unwrap()
+}
+
+#[test]
+fn algorithm_json_shape_is_uniform() {
+    for algo in [
+        Algorithm::Schnorr,
+        Algorithm::Edwards,
+        Algorithm::ECDSA,
+        Algorithm::Unknown(52),
+    ] {
+        let json = serde_json::to_string(&algo).unwrap();
+        assert!(
+            json.starts_with('"') && json.ends_with('"'),
+            "expected {:?} to serialize as a plain string, got `{}`",
+            algo,
+            json
+        );
+
+        let round_tripped: Algorithm = serde_json::from_str(&json).unwrap();
+        assert_eq!(round_tripped, algo);
+    }
+}
+
+#[test]
+fn edwards_signature_is_verified() {
+    let keypair = EdwardsKeypair::generate(&mut rand::thread_rng());
+    let challenge = Challenge::gen_random();
+
+    let address = network_address(49, keypair.public.to_bytes());
+    let raw_sig = keypair.sign(challenge.as_bytes()).to_bytes().to_vec();
+    let sig = Signature::from_bytes(address.algo(), &raw_sig).unwrap();
+
+    assert_eq!(
+        challenge.verify_challenge(&address, &sig),
+        ChallengeVerification::Valid
+    );
+}
+
+#[test]
+fn edwards_signature_from_wrong_key_is_rejected() {
+    let signer = EdwardsKeypair::generate(&mut rand::thread_rng());
+    let owner = EdwardsKeypair::generate(&mut rand::thread_rng());
+    let challenge = Challenge::gen_random();
+
+    // The address names `owner`'s key, but the signature comes from `signer`.
+    let address = network_address(49, owner.public.to_bytes());
+    let raw_sig = signer.sign(challenge.as_bytes()).to_bytes().to_vec();
+    let sig = Signature::from_bytes(address.algo(), &raw_sig).unwrap();
+
+    assert_eq!(
+        challenge.verify_challenge(&address, &sig),
+        ChallengeVerification::Invalid
+    );
+}
+
+#[test]
+fn ecdsa_signature_is_verified_regardless_of_key_parity() {
+    let secp = Secp256k1::new();
+
+    // Run a handful of keypairs so the test isn't at the mercy of drawing
+    // an even-parity key by chance: the address format drops the
+    // compressed key's parity byte entirely, so both parities must verify.
+    for _ in 0..8 {
+        let (secret_key, public_key) = secp.generate_keypair(&mut rand::thread_rng());
+        let challenge = Challenge::gen_random();
+
+        let compressed = public_key.serialize();
+        let mut raw_key = [0u8; 32];
+        raw_key.copy_from_slice(&compressed[1..]);
+        let address = network_address(50, raw_key);
+
+        let digest = blake2_rfc::blake2b::blake2b(32, &[], challenge.as_bytes());
+        let message = secp256k1::Message::from_slice(digest.as_bytes()).unwrap();
+        let raw_sig = secp
+            .sign_ecdsa(&message, &secret_key)
+            .serialize_compact()
+            .to_vec();
+        let sig = Signature::from_bytes(address.algo(), &raw_sig).unwrap();
+
+        assert_eq!(
+            challenge.verify_challenge(&address, &sig),
+            ChallengeVerification::Valid
+        );
+    }
+}
+
+#[test]
+fn ecdsa_signature_from_wrong_key_is_rejected() {
+    let secp = Secp256k1::new();
+    let (_, owner_public) = secp.generate_keypair(&mut rand::thread_rng());
+    let (signer_secret, _) = secp.generate_keypair(&mut rand::thread_rng());
+    let challenge = Challenge::gen_random();
+
+    let compressed = owner_public.serialize();
+    let mut raw_key = [0u8; 32];
+    raw_key.copy_from_slice(&compressed[1..]);
+    let address = network_address(50, raw_key);
+
+    let digest = blake2_rfc::blake2b::blake2b(32, &[], challenge.as_bytes());
+    let message = secp256k1::Message::from_slice(digest.as_bytes()).unwrap();
+    let raw_sig = secp
+        .sign_ecdsa(&message, &signer_secret)
+        .serialize_compact()
+        .to_vec();
+    let sig = Signature::from_bytes(address.algo(), &raw_sig).unwrap();
+
+    assert_eq!(
+        challenge.verify_challenge(&address, &sig),
+        ChallengeVerification::Invalid
+    );
+}
+
+#[test]
+fn unknown_algorithm_is_unsupported_not_invalid() {
+    let address = network_address(200, [0u8; 32]);
+    assert_eq!(*address.algo(), Algorithm::Unknown(200));
+
+    // No signature can even be constructed for an unrecognized scheme.
+    assert!(Signature::from_bytes(address.algo(), &[0u8; 64]).is_err());
+
+    // So exercise `verify_challenge` with a signature of a supported scheme:
+    // because the address' algorithm is unrecognized, it has to be reported
+    // as "unsupported" rather than a plain "invalid".
+    let challenge = Challenge::gen_random();
+    let keypair = schnorrkel::Keypair::generate();
+    let raw_sig = keypair
+        .sign_simple(b"substrate", challenge.as_bytes())
+        .to_bytes()
+        .to_vec();
+    let schnorr_sig = Signature::from_bytes(&Algorithm::Schnorr, &raw_sig).unwrap();
+
+    assert_eq!(
+        challenge.verify_challenge(&address, &schnorr_sig),
+        ChallengeVerification::Unsupported
+    );
+}
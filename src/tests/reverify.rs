@@ -0,0 +1,67 @@
+use crate::manager::{Field, FieldAddress, IdentityField, IdentityFieldType, IdentityState};
+use crate::primitives::{unix_time, Account, ChallengeStatus};
+use crate::reverify::{self, Reverifier, ReverificationScheduler};
+use std::collections::HashMap;
+use std::time::Duration;
+
+struct AlwaysValid;
+
+impl Reverifier for AlwaysValid {
+    fn reverify(&self, _field: &Field) -> crate::primitives::Result<bool> {
+        Ok(true)
+    }
+}
+
+fn interval() -> Duration {
+    Duration::from_secs(60 * 60)
+}
+
+#[test]
+fn jittered_delay_respects_floor_and_upper_bound() {
+    for _ in 0..1000 {
+        let delay = reverify::jittered_delay(interval());
+        assert!(delay >= reverify::MIN_DELAY);
+        assert!(delay < interval() * 2);
+    }
+}
+
+#[test]
+fn freshly_accepted_field_is_not_immediately_due() {
+    let mut field = Field::new(IdentityField::Email(FieldAddress::from(
+        "alice@example.com".to_string(),
+    )));
+    let next_check = unix_time() + reverify::jittered_delay(interval()).as_secs();
+    field.mark_verified(ChallengeStatus::Accepted, next_check);
+
+    let mut fields = HashMap::new();
+    fields.insert(IdentityFieldType::Email, field);
+
+    let identities = vec![IdentityState {
+        net_account: Account::from("alice"),
+        fields,
+    }];
+
+    let scheduler = ReverificationScheduler::new(interval(), AlwaysValid);
+    assert!(scheduler.due(&identities).is_empty());
+}
+
+#[test]
+fn field_overdue_for_next_check_is_due() {
+    let mut field = Field::new(IdentityField::Email(FieldAddress::from(
+        "alice@example.com".to_string(),
+    )));
+    // `next_check` in the past: overdue regardless of when it was last
+    // verified.
+    field.mark_verified(ChallengeStatus::Accepted, 0);
+
+    let mut fields = HashMap::new();
+    fields.insert(IdentityFieldType::Email, field);
+
+    let identities = vec![IdentityState {
+        net_account: Account::from("alice"),
+        fields,
+    }];
+
+    let scheduler = ReverificationScheduler::new(interval(), AlwaysValid);
+    assert_eq!(scheduler.due(&identities).len(), 1);
+}
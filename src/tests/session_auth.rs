@@ -0,0 +1,123 @@
+use crate::primitives::{Challenge, NetworkAddress, Result, Signature};
+use crate::session_auth::{AuthConfig, AuthTransport, ReservedRole, SessionAuthenticator};
+use schnorrkel::Keypair;
+use std::collections::HashMap;
+
+fn network_address(keypair: &Keypair) -> NetworkAddress {
+    // 48 is this crate's key-type byte for Schnorr; see
+    // `NetworkAddress::try_from` in primitives.rs.
+    let encoded = bincode::serialize(&("test-address", 48u8, keypair.public.to_bytes())).unwrap();
+    NetworkAddress::from_bytes(&encoded).unwrap()
+}
+
+/// A fake client that signs whatever nonce it's sent — correctly, or (to
+/// exercise the rejection path) over the wrong message entirely.
+struct FakeClient {
+    keypair: Keypair,
+    sign_correct_message: bool,
+    response: Option<(NetworkAddress, Signature)>,
+}
+
+impl FakeClient {
+    fn new(keypair: Keypair, sign_correct_message: bool) -> Self {
+        FakeClient {
+            keypair,
+            sign_correct_message,
+            response: None,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AuthTransport for FakeClient {
+    async fn send_challenge(&mut self, challenge: &Challenge) -> Result<()> {
+        let signed = if self.sign_correct_message {
+            challenge.as_bytes().to_vec()
+        } else {
+            b"not the challenge you sent me".to_vec()
+        };
+
+        let raw_sig = self
+            .keypair
+            .sign_simple(b"substrate", &signed)
+            .to_bytes()
+            .to_vec();
+
+        let address = network_address(&self.keypair);
+        let sig = Signature::from_bytes(address.algo(), &raw_sig)?;
+        self.response = Some((address, sig));
+
+        Ok(())
+    }
+
+    async fn recv_response(&mut self) -> Result<(NetworkAddress, Signature)> {
+        Ok(self.response.clone().unwrap())
+    }
+}
+
+#[tokio::test]
+async fn disabled_auth_lets_everything_through() {
+    let authenticator = SessionAuthenticator::new(AuthConfig::disabled());
+    let mut client = FakeClient::new(Keypair::generate(), false);
+
+    authenticator
+        .authenticate(&mut client, ReservedRole::Connector)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn permitted_key_with_valid_signature_is_authenticated() {
+    let keypair = Keypair::generate();
+
+    let mut permitted = HashMap::new();
+    permitted.insert(
+        ReservedRole::Connector,
+        vec![network_address(&keypair)],
+    );
+
+    let authenticator = SessionAuthenticator::new(AuthConfig::new(permitted));
+    let mut client = FakeClient::new(keypair, true);
+
+    authenticator
+        .authenticate(&mut client, ReservedRole::Connector)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn invalid_signature_is_rejected() {
+    let keypair = Keypair::generate();
+
+    let mut permitted = HashMap::new();
+    permitted.insert(
+        ReservedRole::Connector,
+        vec![network_address(&keypair)],
+    );
+
+    let authenticator = SessionAuthenticator::new(AuthConfig::new(permitted));
+    let mut client = FakeClient::new(keypair, false);
+
+    assert!(authenticator
+        .authenticate(&mut client, ReservedRole::Connector)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn key_not_permitted_for_role_is_rejected() {
+    let keypair = Keypair::generate();
+
+    // Permitted as an emitter, but not as a connector: an emitter key must
+    // not be able to authenticate as a connector.
+    let mut permitted = HashMap::new();
+    permitted.insert(ReservedRole::Emitter, vec![network_address(&keypair)]);
+
+    let authenticator = SessionAuthenticator::new(AuthConfig::new(permitted));
+    let mut client = FakeClient::new(keypair, true);
+
+    assert!(authenticator
+        .authenticate(&mut client, ReservedRole::Connector)
+        .await
+        .is_err());
+}
@@ -0,0 +1,53 @@
+//! Runs the same `EventStore` exercise against every backend, so the trait
+//! actually proves the in-memory and Postgres stores are interchangeable
+//! rather than each only ever being tested in isolation.
+
+use crate::store::{ConcurrencyConflict, EventStore, InMemoryBackend, PostgresBackend, PostgresConfig};
+use rand::{thread_rng, Rng};
+use serde_json::json;
+
+async fn exercises_event_store(store: &impl EventStore, aggregate_id: &str) {
+    store
+        .append(
+            aggregate_id,
+            None,
+            vec![json!({"value": 1}), json!({"value": 2})],
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    let events = store.load(aggregate_id).await.unwrap();
+    assert_eq!(
+        events,
+        vec![(1, json!({"value": 1})), (2, json!({"value": 2}))]
+    );
+
+    let conflict = store
+        .append(aggregate_id, None, vec![json!({"value": 99})])
+        .await
+        .unwrap();
+    assert_eq!(conflict, Err(ConcurrencyConflict));
+}
+
+#[tokio::test]
+async fn in_memory_backend_satisfies_event_store() {
+    let backend = InMemoryBackend::new();
+    exercises_event_store(&backend, "interop-test").await;
+}
+
+// Requires a real Postgres instance; see `store_postgres::insert_events_against_postgres`.
+#[tokio::test]
+async fn postgres_backend_satisfies_event_store() {
+    let config = match PostgresConfig::from_env() {
+        Some(config) => config,
+        None => {
+            eprintln!("skipping postgres_backend_satisfies_event_store: PGDATABASE is not set");
+            return;
+        }
+    };
+
+    let backend = PostgresBackend::connect(&config).await.unwrap();
+    let aggregate_id = format!("interop-{}", thread_rng().gen::<u64>());
+    exercises_event_store(&backend, &aggregate_id).await;
+}